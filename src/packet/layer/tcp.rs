@@ -1,10 +1,126 @@
 pub use super::{Layer, LayerType, LayerTypes};
-use pnet::packet::tcp::{self, MutableTcpPacket, TcpFlags, TcpOptionPacket, TcpPacket};
+use pnet::packet::tcp::{
+    self, MutableTcpOptionPacket, MutableTcpPacket, TcpFlags, TcpOptionNumbers, TcpOptionPacket,
+    TcpPacket,
+};
+use pnet::packet::Packet;
 use std::clone::Clone;
 use std::fmt::{self, Display, Formatter};
 use std::io;
 use std::net::Ipv4Addr;
 
+/// The largest a TCP options region can be: `data_offset` is a 4-bit count of 4-byte words
+/// covering the 20-byte fixed header plus options, so options top out at `(15 * 4) - 20`.
+const MAX_OPTIONS_SIZE: usize = 40;
+
+/// Represents a parsed TCP option.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TcpOption {
+    /// Maximum segment size.
+    Mss(u16),
+    /// Window scale shift count.
+    WindowScale(u8),
+    /// Selective acknowledgement permitted.
+    SackPermitted,
+    /// Selective acknowledgement block edges, each a (left, right) pair.
+    Sack(Vec<(u32, u32)>),
+    /// Timestamp value and echo reply.
+    Timestamps(u32, u32),
+}
+
+impl TcpOption {
+    /// Converts the `TcpOption` to the raw option used by `pnet`.
+    fn to_raw(&self) -> tcp::TcpOption {
+        match self {
+            TcpOption::Mss(mss) => tcp::TcpOption::mss(*mss),
+            TcpOption::WindowScale(shift) => tcp::TcpOption::wscale(*shift),
+            TcpOption::SackPermitted => tcp::TcpOption::sack_perm(),
+            TcpOption::Sack(edges) => {
+                // The TCP option space tops out at 40 bytes, so a SACK option can carry at
+                // most 4 edge pairs; anything beyond that is dropped instead of wrapping the
+                // length byte (e.g. 32 edges would otherwise encode a length of 2).
+                let edges = &edges[..edges.len().min(4)];
+                let edges: Vec<u32> = edges.iter().flat_map(|(left, right)| [*left, *right]).collect();
+                tcp::TcpOption::selective_ack(&edges)
+            }
+            TcpOption::Timestamps(tsval, tsecr) => tcp::TcpOption::timestamp(*tsval, *tsecr),
+        }
+    }
+
+    /// Parses the raw options of a TCP layer walked by `pnet`, interpreting the kinds this
+    /// proxy understands and bounds-checking each option's payload against the length it
+    /// claims, skipping anything malformed or unrecognized rather than desyncing the walk.
+    fn from_raw(raw: &[tcp::TcpOption]) -> Vec<TcpOption> {
+        let mut options = Vec::new();
+
+        for option in raw {
+            // Options carry their own kind/length/payload privately, so they're walked
+            // through the wire view `pnet` already validated instead of being read directly.
+            let mut buffer = vec![0; TcpOptionPacket::packet_size(option)];
+            if let Some(mut packet) = MutableTcpOptionPacket::new(&mut buffer) {
+                packet.populate(option);
+            } else {
+                continue;
+            }
+            let option = match TcpOptionPacket::new(&buffer) {
+                Some(option) => option,
+                None => continue,
+            };
+            let payload = option.payload();
+
+            match option.get_number() {
+                TcpOptionNumbers::MSS => {
+                    if let [a, b] = *payload {
+                        options.push(TcpOption::Mss(u16::from_be_bytes([a, b])));
+                    }
+                }
+                TcpOptionNumbers::WSCALE => {
+                    if let [shift] = *payload {
+                        options.push(TcpOption::WindowScale(shift));
+                    }
+                }
+                TcpOptionNumbers::SACK_PERMITTED if payload.is_empty() => {
+                    options.push(TcpOption::SackPermitted);
+                }
+                TcpOptionNumbers::SACK if !payload.is_empty() && payload.len() % 8 == 0 => {
+                    let edges = payload
+                        .chunks_exact(8)
+                        .map(|edge| {
+                            let left = u32::from_be_bytes([edge[0], edge[1], edge[2], edge[3]]);
+                            let right = u32::from_be_bytes([edge[4], edge[5], edge[6], edge[7]]);
+                            (left, right)
+                        })
+                        .collect();
+                    options.push(TcpOption::Sack(edges));
+                }
+                TcpOptionNumbers::TIMESTAMPS => {
+                    if let [a, b, c, d, e, f, g, h] = *payload {
+                        let tsval = u32::from_be_bytes([a, b, c, d]);
+                        let tsecr = u32::from_be_bytes([e, f, g, h]);
+                        options.push(TcpOption::Timestamps(tsval, tsecr));
+                    }
+                }
+                // End-of-Options and NOP carry no information and any other kind is not
+                // understood by this proxy, so both are silently skipped.
+                _ => {}
+            }
+        }
+
+        options
+    }
+
+    /// Pads the given raw options with NOPs until their size aligns to a 4-byte boundary.
+    fn pad(mut raw: Vec<tcp::TcpOption>) -> Vec<tcp::TcpOption> {
+        let size: usize = raw.iter().map(TcpOptionPacket::packet_size).sum();
+        let padding = (4 - size % 4) % 4;
+        for _ in 0..padding {
+            raw.push(tcp::TcpOption::nop());
+        }
+
+        raw
+    }
+}
+
 /// Represents a TCP packet.
 #[derive(Clone, Debug)]
 pub struct Tcp {
@@ -67,6 +183,51 @@ impl Tcp {
         tcp
     }
 
+    /// Creates a `Tcp` represents a TCP ACK/SYN carrying the given options.
+    pub fn new_ack_syn_with_options(
+        src_ip_addr: Ipv4Addr,
+        dst_ip_addr: Ipv4Addr,
+        src: u16,
+        dst: u16,
+        sequence: u32,
+        acknowledgement: u32,
+        window: u16,
+        options: &[TcpOption],
+    ) -> Tcp {
+        let mut tcp = Tcp::new_ack_syn(
+            src_ip_addr,
+            dst_ip_addr,
+            src,
+            dst,
+            sequence,
+            acknowledgement,
+            window,
+        );
+        let mut raw = Vec::with_capacity(options.len());
+        let mut size = 0;
+        for option in options.iter().map(TcpOption::to_raw) {
+            // The 40-byte options budget (see `MAX_OPTIONS_SIZE`) is shared across all
+            // options, not just the single largest one: the first option that would overflow
+            // it once combined with what's already queued is dropped along with every option
+            // after it (even ones that would still fit), rather than letting `data_offset`
+            // (a 4-bit wire field) silently wrap past 15 words.
+            let option_size = TcpOptionPacket::packet_size(&option);
+            if size + option_size > MAX_OPTIONS_SIZE {
+                break;
+            }
+            size += option_size;
+            raw.push(option);
+        }
+        let raw = TcpOption::pad(raw);
+        // `populate()` sizes the options region it writes from `data_offset`, and it runs
+        // before `Layer::serialize` recomputes that field from `get_size()`, so it has to be
+        // set here too or serializing a non-empty options list panics.
+        let options_size: usize = raw.iter().map(TcpOptionPacket::packet_size).sum();
+        tcp.layer.data_offset = 5 + (options_size / 4) as u8;
+        tcp.layer.options = raw;
+        tcp
+    }
+
     /// Creates a `Tcp` represents a TCP ACK/RST.
     pub fn new_ack_rst(
         src_ip_addr: Ipv4Addr,
@@ -222,6 +383,27 @@ impl Tcp {
         self.layer.window
     }
 
+    /// Get the options of the layer.
+    pub fn get_options(&self) -> Vec<TcpOption> {
+        TcpOption::from_raw(&self.layer.options)
+    }
+
+    /// Get the negotiated maximum segment size of the layer, if any.
+    pub fn get_mss(&self) -> Option<u16> {
+        self.get_options().into_iter().find_map(|option| match option {
+            TcpOption::Mss(mss) => Some(mss),
+            _ => None,
+        })
+    }
+
+    /// Get the negotiated window scale shift count of the layer, if any.
+    pub fn get_window_scale(&self) -> Option<u8> {
+        self.get_options().into_iter().find_map(|option| match option {
+            TcpOption::WindowScale(shift) => Some(shift),
+            _ => None,
+        })
+    }
+
     /// Returns if the `Tcp` is a TCP acknowledgement.
     pub fn is_ack(&self) -> bool {
         self.layer.flags & TcpFlags::ACK != 0
@@ -343,3 +525,138 @@ impl Layer for Tcp {
         Ok(header_length + n)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pnet::packet::FromPacket;
+
+    #[test]
+    fn tcp_options_round_trip() {
+        let src_ip = Ipv4Addr::new(192, 168, 1, 1);
+        let dst_ip = Ipv4Addr::new(192, 168, 1, 2);
+        let options = vec![
+            TcpOption::Mss(1460),
+            TcpOption::WindowScale(7),
+            TcpOption::SackPermitted,
+            TcpOption::Timestamps(123456, 0),
+        ];
+        let tcp =
+            Tcp::new_ack_syn_with_options(src_ip, dst_ip, 1234, 80, 1, 0, 65535, &options);
+
+        let mut buffer = vec![0u8; tcp.get_size()];
+        tcp.serialize(&mut buffer, 0).unwrap();
+
+        // Options must be padded out to a 4-byte boundary.
+        assert_eq!(buffer.len() % 4, 0);
+
+        let packet = TcpPacket::new(&buffer).unwrap();
+        let parsed = Tcp::parse(&packet, src_ip, dst_ip);
+
+        assert_eq!(parsed.get_mss(), Some(1460));
+        assert_eq!(parsed.get_window_scale(), Some(7));
+        assert!(parsed.get_options().contains(&TcpOption::SackPermitted));
+        assert!(parsed
+            .get_options()
+            .contains(&TcpOption::Timestamps(123456, 0)));
+    }
+
+    #[test]
+    fn tcp_options_sack_is_clamped_to_four_blocks() {
+        let src_ip = Ipv4Addr::new(10, 0, 0, 1);
+        let dst_ip = Ipv4Addr::new(10, 0, 0, 2);
+        let edges: Vec<(u32, u32)> = (0..8u32).map(|i| (i, i + 1)).collect();
+        let tcp = Tcp::new_ack_syn_with_options(
+            src_ip,
+            dst_ip,
+            1,
+            2,
+            0,
+            0,
+            0,
+            &[TcpOption::Sack(edges)],
+        );
+
+        let mut buffer = vec![0u8; tcp.get_size()];
+        tcp.serialize(&mut buffer, 0).unwrap();
+        let packet = TcpPacket::new(&buffer).unwrap();
+        let parsed = Tcp::parse(&packet, src_ip, dst_ip);
+
+        let sack = parsed
+            .get_options()
+            .into_iter()
+            .find_map(|option| match option {
+                TcpOption::Sack(edges) => Some(edges),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(sack, vec![(0, 1), (1, 2), (2, 3), (3, 4)]);
+    }
+
+    #[test]
+    fn tcp_options_over_budget_combination_drops_trailing_options() {
+        let src_ip = Ipv4Addr::new(10, 0, 0, 3);
+        let dst_ip = Ipv4Addr::new(10, 0, 0, 4);
+        let edges: Vec<(u32, u32)> = (0..4u32).map(|i| (i, i + 1)).collect();
+        // Timestamps (10 bytes) plus a full 4-block SACK (34 bytes) is 44 bytes, over the
+        // 40-byte options budget -- exactly the combination RFC 2018 calls out as not
+        // fitting once timestamps are present. The per-option SACK clamp alone doesn't
+        // catch this, since 34 bytes is already a validly-sized single option.
+        let options = vec![TcpOption::Timestamps(1, 2), TcpOption::Sack(edges)];
+        let tcp = Tcp::new_ack_syn_with_options(src_ip, dst_ip, 1, 2, 0, 0, 0, &options);
+
+        let mut buffer = vec![0u8; tcp.get_size()];
+        tcp.serialize(&mut buffer, 0).unwrap();
+        let packet = TcpPacket::new(&buffer).unwrap();
+        let parsed = Tcp::parse(&packet, src_ip, dst_ip);
+
+        assert_eq!(parsed.get_options(), vec![TcpOption::Timestamps(1, 2)]);
+    }
+
+    #[test]
+    fn tcp_options_full_combination_drops_only_what_overflows_the_budget() {
+        let src_ip = Ipv4Addr::new(10, 0, 0, 5);
+        let dst_ip = Ipv4Addr::new(10, 0, 0, 6);
+        let edges: Vec<(u32, u32)> = (0..4u32).map(|i| (i, i + 1)).collect();
+        let options = vec![
+            TcpOption::Mss(1460),
+            TcpOption::WindowScale(7),
+            TcpOption::SackPermitted,
+            TcpOption::Timestamps(1, 2),
+            TcpOption::Sack(edges),
+        ];
+        let tcp = Tcp::new_ack_syn_with_options(src_ip, dst_ip, 1, 2, 0, 0, 0, &options);
+
+        let mut buffer = vec![0u8; tcp.get_size()];
+        tcp.serialize(&mut buffer, 0).unwrap();
+        let packet = TcpPacket::new(&buffer).unwrap();
+        let parsed = Tcp::parse(&packet, src_ip, dst_ip);
+
+        assert_eq!(
+            parsed.get_options(),
+            vec![
+                TcpOption::Mss(1460),
+                TcpOption::WindowScale(7),
+                TcpOption::SackPermitted,
+                TcpOption::Timestamps(1, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn from_raw_drops_malformed_options() {
+        // Hand-built on the wire: MSS claiming 3 payload bytes instead of 2, a SACK
+        // truncated to less than one full edge pair, and a well-formed window scale.
+        let wire: &[&[u8]] = &[
+            &[2, 5, 0x05, 0xb4, 0x00],
+            &[5, 6, 0, 0, 0, 0],
+            &[3, 3, 7],
+        ];
+        let raw: Vec<tcp::TcpOption> = wire
+            .iter()
+            .map(|bytes| TcpOptionPacket::new(bytes).unwrap().from_packet())
+            .collect();
+
+        assert_eq!(TcpOption::from_raw(&raw), vec![TcpOption::WindowScale(7)]);
+    }
+}